@@ -5,34 +5,33 @@ use std::{
     io::{self, Stdout},
     time::Duration,
 };
-use tokio::{
-    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter},
-    net::{
-        UnixStream,
-        unix::{OwnedReadHalf, OwnedWriteHalf},
-    },
-};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter, ReadHalf, WriteHalf};
 use tracing::{error, info, warn};
 
 use crate::{
     domain::{AppMsg, Command},
     root::AppManager,
+    transport::Transport,
     tui::{resume_tui, suspend_tui},
 };
 
 pub struct CharonClient {
     app_mngr: AppManager,
     terminal: Terminal<CrosstermBackend<Stdout>>,
-    reader: BufReader<OwnedReadHalf>,
-    writer: BufWriter<OwnedWriteHalf>,
+    reader: BufReader<ReadHalf<BufReader<Box<dyn Transport>>>>,
+    writer: BufWriter<WriteHalf<BufReader<Box<dyn Transport>>>>,
     should_quit: bool,
 }
 
 impl CharonClient {
-    pub fn new(app_mngr: AppManager, stream: UnixStream) -> Self {
+    /// `transport` is the `BufReader` the auth handshake
+    /// (`crate::transport::connect`) already ran on, carried forward here so
+    /// anything it buffered past the auth challenge isn't lost to a
+    /// freshly-wrapped reader.
+    pub fn new(app_mngr: AppManager, transport: BufReader<Box<dyn Transport>>) -> Self {
         let terminal = ratatui::init();
 
-        let (reader, writer) = stream.into_split();
+        let (reader, writer) = tokio::io::split(transport);
         let writer = BufWriter::new(writer);
         let reader = BufReader::new(reader);
 
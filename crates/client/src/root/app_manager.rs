@@ -10,6 +10,7 @@ pub struct AppManager {
     apps: HashMap<&'static str, Box<dyn UiApp + Send + Sync>>,
     active_id: &'static str,
     is_awake: bool,
+    is_dimmed: bool,
 }
 
 impl AppManager {
@@ -21,6 +22,7 @@ impl AppManager {
             apps,
             active_id,
             is_awake: true,
+            is_dimmed: false,
         }
     }
 
@@ -39,12 +41,17 @@ impl AppManager {
                 self.active_id = Self::mode_screen(mode);
                 Some(Command::Render)
             }
+            AppMsg::Backend(DomainEvent::Dim) => {
+                self.is_dimmed = true;
+                Some(Command::Render)
+            }
             AppMsg::Backend(DomainEvent::Sleep) => {
                 self.is_awake = false;
                 return None;
             }
             AppMsg::Backend(DomainEvent::WakeUp) => {
                 self.is_awake = true;
+                self.is_dimmed = false;
                 return None;
             }
             m => {
@@ -64,6 +71,12 @@ impl AppManager {
         self.apps.contains_key(app)
     }
 
+    /// Whether the daemon reported the keyboard idle long enough to dim, but
+    /// not long enough to go fully to sleep yet.
+    pub fn is_dimmed(&self) -> bool {
+        self.is_dimmed
+    }
+
     pub fn set_active(&mut self, app: &'static str) {
         if self.has_app(app) {
             info!("Activating app: {app}.");
@@ -0,0 +1,94 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::Context;
+use charon_lib::auth::AuthChallenge;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{TcpStream, UnixStream},
+};
+use tokio_rustls::{TlsConnector, rustls};
+use tracing::info;
+
+/// Any duplex byte stream `CharonClient` can speak the newline-delimited
+/// JSON `Event` protocol over. The Unix socket remains the default
+/// transport; TCP and TLS just plug in behind this trait so a remote TUI or
+/// companion app can connect without touching `CharonClient` itself.
+pub trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Transport for T {}
+
+/// Where and how to reach the daemon, picked from `CharonConfig`.
+pub enum TransportConfig {
+    Unix(PathBuf),
+    Tcp(SocketAddr),
+    Tls { addr: SocketAddr, domain: String },
+}
+
+/// Connects using `config`, then runs the challenge/response handshake with
+/// `secret` before handing back the transport. It returns as soon as the
+/// proof is sent, without waiting for the daemon to check it; if the proof
+/// doesn't check out, the daemon closes the connection and the caller sees
+/// the stream end.
+pub async fn connect(
+    config: &TransportConfig,
+    secret: &str,
+) -> anyhow::Result<BufReader<Box<dyn Transport>>> {
+    let stream: Box<dyn Transport> = match config {
+        TransportConfig::Unix(path) => Box::new(
+            UnixStream::connect(path)
+                .await
+                .with_context(|| format!("connecting to {path:?}"))?,
+        ),
+        TransportConfig::Tcp(addr) => Box::new(
+            TcpStream::connect(addr)
+                .await
+                .with_context(|| format!("connecting to {addr}"))?,
+        ),
+        TransportConfig::Tls { addr, domain } => {
+            let tcp = TcpStream::connect(addr)
+                .await
+                .with_context(|| format!("connecting to {addr}"))?;
+            let connector = TlsConnector::from(Arc::new(native_roots_config()?));
+            let server_name = rustls::pki_types::ServerName::try_from(domain.clone())
+                .with_context(|| format!("invalid TLS server name {domain}"))?;
+            Box::new(connector.connect(server_name, tcp).await?)
+        }
+    };
+    authenticate(stream, secret).await
+}
+
+/// Runs the challenge/response handshake and hands back the same
+/// [`BufReader`] it ran the handshake on, rather than the raw stream, so
+/// anything this reader has already buffered past the challenge line stays
+/// readable instead of being dropped with it.
+async fn authenticate(
+    stream: Box<dyn Transport>,
+    secret: &str,
+) -> anyhow::Result<BufReader<Box<dyn Transport>>> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let challenge: AuthChallenge =
+        serde_json::from_str(line.trim()).context("malformed auth challenge")?;
+    let response = challenge.respond(secret)?;
+
+    let mut payload = serde_json::to_string(&response)?;
+    payload.push('\n');
+    reader.write_all(payload.as_bytes()).await?;
+    reader.flush().await?;
+
+    info!("Authenticated with daemon");
+    Ok(reader)
+}
+
+fn native_roots_config() -> anyhow::Result<rustls::ClientConfig> {
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in rustls_native_certs::load_native_certs().context("loading native roots")? {
+        roots
+            .add(cert)
+            .map_err(|err| anyhow::anyhow!("invalid root certificate: {err}"))?;
+    }
+    Ok(rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+
+use charon_lib::event::{DomainEvent, Event, Topic};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::warn;
+
+use crate::domain::Caveats;
+
+struct Subscriber {
+    name: Cow<'static, str>,
+    topics: &'static [Topic],
+    caveats: Caveats,
+    tx: Sender<Event>,
+}
+
+/// Fans every incoming [`Event`] out to whichever actors subscribed to its
+/// [`Topic`], running each subscriber's [`Caveats`] first so a subscription
+/// only ever gets the least-privilege slice of the stream it was granted.
+pub struct EventBroker {
+    rx: Receiver<Event>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl EventBroker {
+    pub fn new(rx: Receiver<Event>) -> Self {
+        Self {
+            rx,
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Subscribes `tx` to every event on `topics`, unfiltered.
+    pub fn add_subscriber(
+        &mut self,
+        tx: Sender<Event>,
+        name: Cow<'static, str>,
+        topics: &'static [Topic],
+    ) {
+        self.add_subscriber_with_caveats(tx, name, topics, Caveats::none());
+    }
+
+    /// Subscribes `tx` to `topics`, additionally narrowing or rewriting every
+    /// event through `caveats` before delivery.
+    pub fn add_subscriber_with_caveats(
+        &mut self,
+        tx: Sender<Event>,
+        name: Cow<'static, str>,
+        topics: &'static [Topic],
+        caveats: Caveats,
+    ) {
+        self.subscribers.push(Subscriber {
+            name,
+            topics,
+            caveats,
+            tx,
+        });
+    }
+
+    /// Drains incoming events into subscribers until the channel closes or a
+    /// `DomainEvent::Exit` is broadcast.
+    pub async fn run(&mut self) {
+        while let Some(event) = self.rx.recv().await {
+            let is_exit = matches!(event.payload, DomainEvent::Exit);
+            self.broadcast(&event, is_exit).await;
+            if is_exit {
+                break;
+            }
+        }
+    }
+
+    /// Delivers `event` to every subscriber whose `topics` include it (or
+    /// every subscriber regardless of topic, if `force`), applying each
+    /// subscriber's [`Caveats`] first and skipping delivery entirely if they
+    /// drop the event.
+    pub async fn broadcast(&mut self, event: &Event, force: bool) {
+        let topic = Topic::from(&event.payload);
+        for subscriber in &self.subscribers {
+            if !force && !subscriber.topics.contains(&topic) {
+                continue;
+            }
+            let Some(payload) = subscriber.caveats.apply(&event.payload) else {
+                continue;
+            };
+            let scoped = Event::new(event.origin.clone(), payload);
+            if subscriber.tx.send(scoped).await.is_err() {
+                warn!("Subscriber {} dropped its channel", subscriber.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::transport::ListenerConfig;
+
+/// Runtime configuration threaded through every actor. Cheap to clone: each
+/// actor gets its own copy, so per-keyboard overrides don't need a shared
+/// lock.
+#[derive(Debug, Clone)]
+pub struct CharonConfig {
+    keyboard_names: Vec<String>,
+    idle_dim_timeout: Duration,
+    idle_sleep_timeout: Duration,
+    transport: ListenerConfig,
+    auth_secret: Arc<str>,
+}
+
+impl Default for CharonConfig {
+    fn default() -> Self {
+        Self {
+            keyboard_names: Vec::new(),
+            idle_dim_timeout: Duration::from_secs(30),
+            idle_sleep_timeout: Duration::from_secs(300),
+            transport: ListenerConfig::default(),
+            auth_secret: Arc::from(""),
+        }
+    }
+}
+
+impl CharonConfig {
+    /// One `(name, config)` pair per configured keyboard, handed to
+    /// `Daemon::add_scanners` to spawn one `KeyScanner` each.
+    pub fn get_config_per_keyboard(&self) -> Vec<(String, CharonConfig)> {
+        self.keyboard_names
+            .iter()
+            .cloned()
+            .map(|name| (name, self.clone()))
+            .collect()
+    }
+
+    pub fn add_keyboard(&mut self, name: impl Into<String>) -> &mut Self {
+        self.keyboard_names.push(name.into());
+        self
+    }
+
+    /// How long the keyboard may go without a `KeyInput` event before
+    /// `IdleMonitor` dims the UI.
+    pub fn idle_dim_timeout(&self) -> Duration {
+        self.idle_dim_timeout
+    }
+
+    /// How long the keyboard may go without a `KeyInput` event, counted from
+    /// the last one (not from dimming), before `IdleMonitor` broadcasts
+    /// `Sleep`. Must be >= `idle_dim_timeout`.
+    pub fn idle_sleep_timeout(&self) -> Duration {
+        self.idle_sleep_timeout
+    }
+
+    pub fn set_idle_dim_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.idle_dim_timeout = timeout;
+        self
+    }
+
+    pub fn set_idle_sleep_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.idle_sleep_timeout = timeout;
+        self
+    }
+
+    /// Which transport `Daemon::listen` binds: Unix socket (default), plain
+    /// TCP, or TLS-wrapped TCP.
+    pub fn transport(&self) -> &ListenerConfig {
+        &self.transport
+    }
+
+    pub fn set_transport(&mut self, transport: ListenerConfig) -> &mut Self {
+        self.transport = transport;
+        self
+    }
+
+    /// The pre-shared secret clients must prove knowledge of during the
+    /// auth handshake before they can inject `DomainEvent`s.
+    pub fn auth_secret(&self) -> Arc<str> {
+        self.auth_secret.clone()
+    }
+
+    pub fn set_auth_secret(&mut self, secret: impl Into<Arc<str>>) -> &mut Self {
+        self.auth_secret = secret.into();
+        self
+    }
+}
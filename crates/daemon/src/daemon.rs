@@ -1,32 +1,38 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, path::PathBuf, sync::Arc};
 
 use charon_lib::event::{DomainEvent, Event, Mode, Topic};
 use tokio::{
-    sync::{
-        RwLock,
-        mpsc::{self, Sender},
-    },
+    io::{AsyncBufReadExt, BufReader},
+    sync::mpsc::{self, Sender},
     task::JoinHandle,
 };
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     actor::{KeyScanner, Pipeline},
     broker::EventBroker,
     config::CharonConfig,
+    dataspace::{Dataspace, DataspaceHandle, Fact},
     domain::{
-        ActorState, ProcessorState,
+        ActorState, Caveats, ProcessorState,
         traits::{Actor, Processor},
     },
+    idle::IdleMonitor,
+    lua_processor::LuaProcessor,
+    transport::{self, Transport},
 };
 
 type ProcessorCtor = fn(ProcessorState) -> Box<dyn Processor + Send + Sync>;
 
+/// Owner name under which the daemon asserts the current [`Mode`] into the
+/// dataspace — the replacement for the old `Arc<RwLock<Mode>>` shared field.
+const MODE_OWNER: &str = "daemon";
+
 pub struct Daemon {
-    tasks: Vec<JoinHandle<()>>,
+    tasks: Vec<(Cow<'static, str>, JoinHandle<()>)>,
     broker: EventBroker,
     event_tx: Sender<Event>,
-    mode: Arc<RwLock<Mode>>,
+    dataspace: DataspaceHandle,
     config: CharonConfig,
 }
 
@@ -37,14 +43,46 @@ impl Daemon {
             tasks: Vec::new(),
             broker: EventBroker::new(broker_rx),
             event_tx,
-            mode: Arc::new(RwLock::new(Mode::PassThrough)),
+            dataspace: Dataspace::handle_with_mode(Mode::PassThrough),
             config: CharonConfig::default(),
         }
     }
 
+    /// Changes the daemon's operating mode: retracts the previous
+    /// `Fact::Mode` and asserts `mode` in its place, notifying any actor
+    /// subscribed to `Fact::Mode` changes.
+    pub async fn set_mode(&self, mode: Mode) {
+        let mut dataspace = self.dataspace.write().await;
+        let previous = dataspace
+            .facts_of(MODE_OWNER)
+            .iter()
+            .find_map(|fact| match fact {
+                Fact::Mode(mode) => Some(*mode),
+                _ => None,
+            });
+        if let Some(previous) = previous {
+            dataspace.retract(MODE_OWNER, &Fact::Mode(previous));
+        }
+        dataspace.assert(MODE_OWNER.into(), Fact::Mode(mode));
+    }
+
     pub async fn run(&mut self) {
         info!("Charon is ready...");
-        self.broker.run().await;
+
+        let listen = Self::listen_with(
+            self.config.transport().clone(),
+            self.config.auth_secret(),
+            self.event_tx.clone(),
+        );
+
+        tokio::select! {
+            _ = self.broker.run() => {}
+            result = listen => {
+                if let Err(err) = result {
+                    error!("Transport listener stopped: {err}");
+                }
+            }
+        }
         self.stop().await;
     }
 
@@ -54,10 +92,13 @@ impl Daemon {
     }
 
     pub async fn shutdown(&mut self) {
-        for handle in self.tasks.drain(..) {
+        for (name, handle) in self.tasks.drain(..) {
             if let Err(err) = handle.await {
                 error!("Error while sutting down an actor: {err}");
             }
+            // Facts live only while their owner is alive: once its task has
+            // joined, drop everything it asserted and notify observers.
+            self.dataspace.write().await.retract_all(&name);
         }
     }
 
@@ -73,14 +114,14 @@ impl Daemon {
         self.broker.add_subscriber(pt_tx, name.clone(), topics);
         let state = ActorState::new(
             name.clone(),
-            self.mode.clone(),
+            self.dataspace.clone(),
             self.event_tx.clone(),
             pt_rx,
             config,
             processors,
         );
         match T::spawn(state, init) {
-            Ok(task) => self.tasks.push(task),
+            Ok(task) => self.tasks.push((name, task)),
             Err(err) => error!("Couldn't spawn an actor {name} due to error: {err}"),
         }
         self
@@ -96,6 +137,34 @@ impl Daemon {
         )
     }
 
+    /// Like [`Daemon::add_actor`], but the subscriber only gets the
+    /// least-privilege slice of the stream its `caveats` allow through. Use
+    /// this to hand a plugin or third-party actor a narrowed view instead of
+    /// every event on `topics`.
+    pub fn add_actor_with_caveats<T: Actor<Init = ()>>(
+        &mut self,
+        topics: &'static [Topic],
+        caveats: Caveats,
+    ) -> &mut Self {
+        let name: Cow<'static, str> = T::name().into();
+        let (pt_tx, pt_rx) = mpsc::channel::<Event>(128);
+        self.broker
+            .add_subscriber_with_caveats(pt_tx, name.clone(), topics, caveats);
+        let state = ActorState::new(
+            name.clone(),
+            self.dataspace.clone(),
+            self.event_tx.clone(),
+            pt_rx,
+            self.config.clone(),
+            Vec::new(),
+        );
+        match T::spawn(state, ()) {
+            Ok(task) => self.tasks.push((name, task)),
+            Err(err) => error!("Couldn't spawn an actor {} due to error: {err}", T::name()),
+        }
+        self
+    }
+
     pub fn add_actor_conditionally<T: Actor<Init = ()>>(
         &mut self,
         should_add: bool,
@@ -107,6 +176,13 @@ impl Daemon {
         self
     }
 
+    /// Registers the idle-timeout actor: after `CharonConfig`'s configured
+    /// idle durations pass with no `KeyInput`, the daemon broadcasts `Dim`
+    /// then `Sleep`; any subsequent key event broadcasts `WakeUp`.
+    pub fn add_idle_monitor(&mut self) -> &mut Self {
+        self.add_actor_with_init::<IdleMonitor>((), &[Topic::KeyInput])
+    }
+
     pub fn add_scanners(&mut self, topics: &'static [Topic]) -> &mut Self {
         for (name, config) in self.config.get_config_per_keyboard() {
             debug!("Registering scanner: {name}");
@@ -140,7 +216,8 @@ impl Daemon {
         topics: &'static [Topic],
         factories: &[ProcessorCtor],
     ) -> &mut Self {
-        let state = ProcessorState::new(T::name().into(), self.mode.clone(), self.config.clone());
+        let state =
+            ProcessorState::new(T::name().into(), self.dataspace.clone(), self.config.clone());
         let processors: Vec<_> = factories.iter().map(|f| f(state.clone())).collect();
         self.register_actor::<T>(
             T::name().into(),
@@ -157,12 +234,31 @@ impl Daemon {
         topics: &'static [Topic],
         factories: &[ProcessorCtor],
     ) -> &mut Self {
-        let state = ProcessorState::new(name.into(), self.mode.clone(), self.config.clone());
+        let state = ProcessorState::new(name.into(), self.dataspace.clone(), self.config.clone());
         let processors: Vec<_> = factories.iter().map(|f| f(state.clone())).collect();
         self.register_actor::<Pipeline>(name.into(), (), topics, self.config.clone(), processors);
         self
     }
 
+    /// Registers a pipeline backed by a Lua script instead of a compiled
+    /// [`Processor`]: the script is loaded once up-front and re-read whenever
+    /// its mtime changes, so key-remaps, leader-key macros and conditional
+    /// filters can be written and tweaked without recompiling Charon.
+    pub fn add_lua_pipeline(
+        &mut self,
+        name: &'static str,
+        topics: &'static [Topic],
+        script_path: impl Into<PathBuf>,
+    ) -> &mut Self {
+        let state = ProcessorState::new(name.into(), self.dataspace.clone(), self.config.clone());
+        let processor: Box<dyn Processor + Send + Sync> =
+            Box::new(LuaProcessor::new(state, name, script_path.into()));
+        self.register_actor::<Pipeline>(name.into(), (), topics, self.config.clone(), vec![
+            processor,
+        ]);
+        self
+    }
+
     pub fn update_config(&mut self, transform_cfg: fn(&mut CharonConfig)) -> &mut Self {
         (transform_cfg)(&mut self.config);
         self
@@ -172,4 +268,61 @@ impl Daemon {
         self.config = config;
         self
     }
+
+    /// The shared dataspace actors assert presence/state facts into (e.g.
+    /// connected keyboards, active app, battery) and subscribe to for
+    /// assert/retract notifications, instead of polling a lock.
+    pub fn dataspace(&self) -> DataspaceHandle {
+        self.dataspace.clone()
+    }
+
+    /// Binds the transport selected in `CharonConfig` (Unix socket by
+    /// default, optionally TCP or TLS) and forwards every authenticated
+    /// client's `Event`s into the broker, so a remote TUI or companion app
+    /// can inject `DomainEvent`s the same way a local client does. Already
+    /// wired into `run()`; exposed separately so the listener can be driven
+    /// on its own, e.g. in tests.
+    pub async fn listen(&self) -> anyhow::Result<()> {
+        Self::listen_with(
+            self.config.transport().clone(),
+            self.config.auth_secret(),
+            self.event_tx.clone(),
+        )
+        .await
+    }
+
+    async fn listen_with(
+        config: transport::ListenerConfig,
+        secret: Arc<str>,
+        event_tx: Sender<Event>,
+    ) -> anyhow::Result<()> {
+        transport::serve(config, secret, move |reader| {
+            let event_tx = event_tx.clone();
+            async move {
+                if let Err(err) = Self::relay_client(reader, event_tx).await {
+                    warn!("Client connection ended: {err}");
+                }
+            }
+        })
+        .await
+    }
+
+    /// Takes the same `BufReader` the auth handshake ran on, so any bytes of
+    /// the client's first `Event` it already buffered past the auth response
+    /// aren't lost to a freshly-wrapped reader.
+    async fn relay_client(
+        mut reader: BufReader<Box<dyn Transport>>,
+        event_tx: Sender<Event>,
+    ) -> anyhow::Result<()> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                break;
+            }
+            let event: Event = serde_json::from_str(line.trim())?;
+            event_tx.send(event).await?;
+        }
+        Ok(())
+    }
 }
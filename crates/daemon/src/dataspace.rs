@@ -0,0 +1,136 @@
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+use charon_lib::event::Mode;
+use tokio::sync::{
+    RwLock,
+    mpsc::{self, Receiver, Sender},
+};
+
+/// A piece of state an actor *asserts* into the [`Dataspace`]: which
+/// keyboards are connected, which app is active, current battery level, the
+/// operating mode. Facts are owned by whichever actor asserted them and stay
+/// live only while that actor is alive.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fact {
+    Mode(Mode),
+    ActiveApp(Cow<'static, str>),
+    ConnectedKeyboard(Cow<'static, str>),
+    Battery(u8),
+}
+
+/// Emitted to pattern subscribers whenever a matching fact is asserted or
+/// retracted.
+#[derive(Debug, Clone)]
+pub enum Notification {
+    Asserted { owner: Cow<'static, str>, fact: Fact },
+    Retracted { owner: Cow<'static, str>, fact: Fact },
+}
+
+impl Notification {
+    fn fact(&self) -> &Fact {
+        match self {
+            Notification::Asserted { fact, .. } => fact,
+            Notification::Retracted { fact, .. } => fact,
+        }
+    }
+}
+
+type Pattern = Box<dyn Fn(&Fact) -> bool + Send + Sync>;
+
+/// A shared dataspace of facts keyed by `(actor_name, fact)`, replacing
+/// ad-hoc `Arc<RwLock<T>>` state shared between actors. Actors assert facts
+/// they own and retract them themselves, or rely on the daemon to retract
+/// everything they own once their `JoinHandle` completes (see
+/// `Daemon::shutdown`). Other actors observe facts via pattern subscriptions
+/// instead of polling a lock.
+#[derive(Default)]
+pub struct Dataspace {
+    facts: HashMap<Cow<'static, str>, Vec<Fact>>,
+    subscribers: Vec<(Pattern, Sender<Notification>)>,
+}
+
+impl Dataspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle() -> DataspaceHandle {
+        Arc::new(RwLock::new(Self::new()))
+    }
+
+    /// Builds a handle with `Fact::Mode(mode)` pre-asserted under the
+    /// `"daemon"` owner, so actors reading the mode before anything else
+    /// touches the dataspace still see a sensible default instead of `None`.
+    pub fn handle_with_mode(mode: Mode) -> DataspaceHandle {
+        let mut dataspace = Self::new();
+        dataspace.assert(Cow::Borrowed("daemon"), Fact::Mode(mode));
+        Arc::new(RwLock::new(dataspace))
+    }
+
+    /// Asserts `fact` as owned by `owner`, notifying matching subscribers.
+    pub fn assert(&mut self, owner: Cow<'static, str>, fact: Fact) {
+        self.facts.entry(owner.clone()).or_default().push(fact.clone());
+        self.notify(Notification::Asserted { owner, fact });
+    }
+
+    /// Retracts one fact previously asserted by `owner`.
+    pub fn retract(&mut self, owner: &str, fact: &Fact) {
+        if let Some(facts) = self.facts.get_mut(owner) {
+            facts.retain(|f| f != fact);
+        }
+        self.notify(Notification::Retracted {
+            owner: Cow::Owned(owner.to_owned()),
+            fact: fact.clone(),
+        });
+    }
+
+    /// Retracts every fact owned by `owner`. Called by the daemon once an
+    /// actor's task has exited, so presence/state tracking never needs
+    /// manual teardown.
+    pub fn retract_all(&mut self, owner: &str) {
+        if let Some(facts) = self.facts.remove(owner) {
+            for fact in facts {
+                self.notify(Notification::Retracted {
+                    owner: Cow::Owned(owner.to_owned()),
+                    fact,
+                });
+            }
+        }
+    }
+
+    pub fn facts_of(&self, owner: &str) -> &[Fact] {
+        self.facts.get(owner).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Subscribes to facts matching `pattern`, receiving an assert/retract
+    /// notification every time one is added or removed.
+    pub fn subscribe(
+        &mut self,
+        pattern: impl Fn(&Fact) -> bool + Send + Sync + 'static,
+    ) -> Receiver<Notification> {
+        let (tx, rx) = mpsc::channel(32);
+        self.subscribers.push((Box::new(pattern), tx));
+        rx
+    }
+
+    fn notify(&mut self, notification: Notification) {
+        use mpsc::error::TrySendError;
+
+        self.subscribers.retain(|(pattern, tx)| {
+            if !pattern(notification.fact()) {
+                return true;
+            }
+            // A full channel just means the subscriber hasn't drained yet;
+            // it's still alive and should keep observing future facts. Only
+            // a closed channel means the subscriber is actually gone.
+            match tx.try_send(notification.clone()) {
+                Ok(()) | Err(TrySendError::Full(_)) => true,
+                Err(TrySendError::Closed(_)) => false,
+            }
+        });
+    }
+}
+
+/// Shared handle to a [`Dataspace`], cloned into every actor's state the
+/// same way `Arc<RwLock<Mode>>` used to be.
+pub type DataspaceHandle = Arc<RwLock<Dataspace>>;
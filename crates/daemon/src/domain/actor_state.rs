@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::error;
+
+use charon_lib::event::{DomainEvent, Event, Mode};
+
+use crate::{
+    config::CharonConfig,
+    dataspace::{DataspaceHandle, Fact, Notification},
+};
+
+use super::traits::Processor;
+
+/// Everything an [`Actor`](super::traits::Actor) needs once spawned: its own
+/// inbound event stream (already filtered to its subscribed `Topic`s and
+/// narrowed by any `Caveats`), a way to broadcast new events back through the
+/// broker, its [`CharonConfig`], any [`Processor`]s it should run incoming
+/// events through, and a handle to the shared [`Dataspace`](crate::dataspace::Dataspace)
+/// to assert/retract facts and subscribe to others' — the replacement for
+/// ad-hoc `Arc<RwLock<T>>` state shared between actors.
+pub struct ActorState {
+    name: Cow<'static, str>,
+    dataspace: DataspaceHandle,
+    event_tx: Sender<Event>,
+    event_rx: Receiver<Event>,
+    config: CharonConfig,
+    processors: Vec<Box<dyn Processor + Send + Sync>>,
+}
+
+impl ActorState {
+    pub fn new(
+        name: Cow<'static, str>,
+        dataspace: DataspaceHandle,
+        event_tx: Sender<Event>,
+        event_rx: Receiver<Event>,
+        config: CharonConfig,
+        processors: Vec<Box<dyn Processor + Send + Sync>>,
+    ) -> Self {
+        Self {
+            name,
+            dataspace,
+            event_tx,
+            event_rx,
+            config,
+            processors,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn config(&self) -> &CharonConfig {
+        &self.config
+    }
+
+    pub fn processors(&mut self) -> &mut [Box<dyn Processor + Send + Sync>] {
+        &mut self.processors
+    }
+
+    /// Awaits the next event on this actor's (already filtered) subscription.
+    pub async fn recv_event(&mut self) -> Option<Event> {
+        self.event_rx.recv().await
+    }
+
+    /// Broadcasts `payload` back through the daemon, originating from this
+    /// actor.
+    pub async fn broadcast(&self, payload: DomainEvent) {
+        let event = Event::new(self.name.to_string(), payload);
+        if let Err(err) = self.event_tx.send(event).await {
+            error!("{}: couldn't broadcast event: {err}", self.name);
+        }
+    }
+
+    /// The daemon's current operating mode, read from the dataspace's
+    /// `Fact::Mode` (owned by `"daemon"`) instead of a shared lock.
+    pub async fn mode(&self) -> Mode {
+        self.dataspace
+            .read()
+            .await
+            .facts_of("daemon")
+            .iter()
+            .find_map(|fact| match fact {
+                Fact::Mode(mode) => Some(*mode),
+                _ => None,
+            })
+            .unwrap_or(Mode::PassThrough)
+    }
+
+    /// Asserts `fact` into the dataspace, owned by this actor.
+    pub async fn assert(&self, fact: Fact) {
+        self.dataspace.write().await.assert(self.name.clone(), fact);
+    }
+
+    /// Retracts a fact this actor previously asserted.
+    pub async fn retract(&self, fact: &Fact) {
+        self.dataspace.write().await.retract(&self.name, fact);
+    }
+
+    /// Subscribes to facts matching `pattern`; see
+    /// [`Dataspace::subscribe`](crate::dataspace::Dataspace::subscribe).
+    pub async fn subscribe(
+        &self,
+        pattern: impl Fn(&Fact) -> bool + Send + Sync + 'static,
+    ) -> Receiver<Notification> {
+        self.dataspace.write().await.subscribe(pattern)
+    }
+}
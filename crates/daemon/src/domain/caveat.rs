@@ -0,0 +1,94 @@
+use charon_lib::event::DomainEvent;
+
+use super::{HidKeyCode, Modifiers};
+
+/// A single rule checked against a `DomainEvent` before it is delivered to a
+/// subscriber: a matcher that decides whether the event passes, and an
+/// optional transform applied to events that do. This is what lets a
+/// subscription narrow a coarse `Topic` filter down to a least-privilege view
+/// of the stream, e.g. "only `KeyPress` with Super held".
+pub struct Caveat {
+    matcher: Box<dyn Fn(&DomainEvent) -> bool + Send + Sync>,
+    transform: Option<Box<dyn Fn(DomainEvent) -> DomainEvent + Send + Sync>>,
+}
+
+impl Caveat {
+    pub fn new(matcher: impl Fn(&DomainEvent) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            matcher: Box::new(matcher),
+            transform: None,
+        }
+    }
+
+    /// Rewrites events that pass the matcher before delivery.
+    pub fn with_transform(
+        mut self,
+        transform: impl Fn(DomainEvent) -> DomainEvent + Send + Sync + 'static,
+    ) -> Self {
+        self.transform = Some(Box::new(transform));
+        self
+    }
+
+    /// Only `KeyPress`/`KeyRelease` events whose modifier set contains every
+    /// modifier in `required`.
+    pub fn modifiers_contain(required: Modifiers) -> Self {
+        Self::new(move |event| match event {
+            DomainEvent::KeyPress(_, modifiers) | DomainEvent::KeyRelease(_, modifiers) => {
+                modifiers.contains(&required)
+            }
+            _ => false,
+        })
+    }
+
+    /// Only `KeyPress`/`KeyRelease` events whose keycode is in `allowed`.
+    pub fn keycode_in(allowed: &'static [HidKeyCode]) -> Self {
+        Self::new(move |event| match event {
+            DomainEvent::KeyPress(code, _) | DomainEvent::KeyRelease(code, _) => {
+                allowed.contains(code)
+            }
+            _ => false,
+        })
+    }
+
+    /// Applies this caveat to `event`, returning the (possibly rewritten)
+    /// event if it passes, or `None` if it should be dropped.
+    fn apply(&self, event: DomainEvent) -> Option<DomainEvent> {
+        if !(self.matcher)(&event) {
+            return None;
+        }
+        Some(match &self.transform {
+            Some(transform) => transform(event),
+            None => event,
+        })
+    }
+}
+
+/// The caveats attached to one subscription: evaluated in order in the
+/// broker's fan-out path, each narrowing or rewriting the event before the
+/// next runs. A subscription with no caveats behaves exactly like a plain
+/// `Topic` filter did before caveats existed.
+#[derive(Default)]
+pub struct Caveats(Vec<Caveat>);
+
+impl Caveats {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn new(caveats: Vec<Caveat>) -> Self {
+        Self(caveats)
+    }
+
+    /// Runs `event` through every caveat; it is delivered only if all of them
+    /// match, picking up any transforms along the way.
+    pub fn apply(&self, event: &DomainEvent) -> Option<DomainEvent> {
+        if self.0.is_empty() {
+            return Some(event.clone());
+        }
+        let mut current = event.clone();
+        for caveat in &self.0 {
+            current = caveat.apply(current)?;
+        }
+        Some(current)
+    }
+}
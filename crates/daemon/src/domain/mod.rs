@@ -1,4 +1,5 @@
 mod actor_state;
+mod caveat;
 mod hid_keycode;
 mod key_shortcut;
 mod keyboard_state;
@@ -7,6 +8,7 @@ mod processor_state;
 pub mod traits;
 
 pub use actor_state::ActorState;
+pub use caveat::{Caveat, Caveats};
 pub use hid_keycode::HidKeyCode;
 pub use key_shortcut::KeyShortcut;
 pub use keyboard_state::KeyboardState;
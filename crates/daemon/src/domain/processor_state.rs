@@ -0,0 +1,75 @@
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use charon_lib::event::Mode;
+
+use crate::{
+    config::CharonConfig,
+    dataspace::{DataspaceHandle, Fact},
+};
+
+/// Hands out one id per [`ProcessorState`] constructed, process-wide. Each
+/// actor registration (`Daemon::add_pipeline`, `add_lua_pipeline`, ...)
+/// builds its own `ProcessorState`, so two pipelines always get distinct
+/// ids — which is what lets
+/// [`TextBufferProcessor`](crate::text_buffer::TextBufferProcessor) use this
+/// as a WOOT [`WootId`](charon_lib::text::WootId) site id without two local
+/// replicas ever colliding.
+static NEXT_SITE_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Context handed to every [`Processor`](super::traits::Processor) in a
+/// pipeline: its name (for logging), the daemon's [`CharonConfig`], a handle
+/// to the shared dataspace to read the live [`Mode`] from, and a site id
+/// unique to this registration. Cheap to clone — processors in the same
+/// pipeline share the same dataspace handle but keep the `site_id` assigned
+/// when the pipeline was built.
+#[derive(Clone)]
+pub struct ProcessorState {
+    name: Cow<'static, str>,
+    dataspace: DataspaceHandle,
+    config: CharonConfig,
+    site_id: u32,
+}
+
+impl ProcessorState {
+    pub fn new(name: Cow<'static, str>, dataspace: DataspaceHandle, config: CharonConfig) -> Self {
+        Self {
+            name,
+            dataspace,
+            config,
+            site_id: NEXT_SITE_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn config(&self) -> &CharonConfig {
+        &self.config
+    }
+
+    /// Best-effort read of the current [`Mode`], asserted as a `Fact::Mode`
+    /// owned by `"daemon"`; falls back to [`Mode::PassThrough`] if the
+    /// dataspace lock is contended or no mode has been asserted yet, since
+    /// `Processor::process` is synchronous and can't await it.
+    pub fn mode(&self) -> Mode {
+        self.dataspace
+            .try_read()
+            .ok()
+            .and_then(|dataspace| {
+                dataspace.facts_of("daemon").iter().find_map(|fact| match fact {
+                    Fact::Mode(mode) => Some(*mode),
+                    _ => None,
+                })
+            })
+            .unwrap_or(Mode::PassThrough)
+    }
+
+    /// Unique id for this registration, used as a WOOT site id.
+    pub fn site_id(&self) -> u32 {
+        self.site_id
+    }
+}
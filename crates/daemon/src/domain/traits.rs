@@ -0,0 +1,34 @@
+use anyhow::Error;
+use tokio::task::JoinHandle;
+
+use charon_lib::event::DomainEvent;
+
+use super::ActorState;
+
+/// A long-running unit the [`Daemon`](crate::daemon::Daemon) spawns and
+/// supervises: a [`KeyScanner`](crate::actor::KeyScanner) reading one
+/// physical keyboard, the Lua/compiled [`Pipeline`](crate::actor::Pipeline),
+/// [`IdleMonitor`](crate::idle::IdleMonitor), and so on. `spawn` gets an
+/// [`ActorState`] already wired into the broker and dataspace, plus whatever
+/// per-actor init value `Init` is.
+pub trait Actor {
+    type Init;
+
+    /// Stable name used for logging. It is also the registration name, and so
+    /// the owner of any facts the actor asserts into the dataspace, unless the
+    /// daemon registers the actor under its own name (`KeyScanner-<keyboard>`
+    /// for scanners, the pipeline name for pipelines).
+    fn name() -> &'static str;
+
+    fn spawn(state: ActorState, init: Self::Init) -> Result<JoinHandle<()>, Error>;
+}
+
+/// One stage in a [`Pipeline`](crate::actor::Pipeline): transforms an
+/// incoming [`DomainEvent`] into zero or more outgoing ones. Compiled
+/// processors and [`LuaProcessor`](crate::lua_processor::LuaProcessor) both
+/// implement this so a pipeline can mix the two freely.
+pub trait Processor {
+    fn name(&self) -> &str;
+
+    fn process(&mut self, event: &DomainEvent) -> Vec<DomainEvent>;
+}
@@ -0,0 +1,77 @@
+use tokio::time::{Duration, Instant, sleep_until};
+use tracing::info;
+
+use charon_lib::event::DomainEvent;
+
+use crate::domain::{ActorState, traits::Actor};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Awareness {
+    Awake,
+    Dimmed,
+    Asleep,
+}
+
+/// Watches `KeyInput` traffic and broadcasts `Dim`/`Sleep`/`WakeUp` once the
+/// keyboard has been idle for the durations configured in `CharonConfig`, so
+/// the TUI can dim and scanners can power down on their own. Any `KeyInput`
+/// event resets the timer and, if the daemon had dimmed or slept, broadcasts
+/// `WakeUp`.
+pub struct IdleMonitor;
+
+impl Actor for IdleMonitor {
+    type Init = ();
+
+    fn name() -> &'static str {
+        "IdleMonitor"
+    }
+
+    fn spawn(
+        mut state: ActorState,
+        _init: (),
+    ) -> Result<tokio::task::JoinHandle<()>, anyhow::Error> {
+        let dim_after = state.config().idle_dim_timeout();
+        let sleep_after = state.config().idle_sleep_timeout();
+
+        Ok(tokio::spawn(async move {
+            let mut awareness = Awareness::Awake;
+            let mut deadline = Instant::now() + dim_after;
+
+            loop {
+                tokio::select! {
+                    event = state.recv_event() => {
+                        match event {
+                            Some(event) if matches!(event.payload, DomainEvent::Exit) => break,
+                            Some(_) => {
+                                deadline = Instant::now() + dim_after;
+                                if awareness != Awareness::Awake {
+                                    info!("Activity resumed, waking {} up", IdleMonitor::name());
+                                    awareness = Awareness::Awake;
+                                    state.broadcast(DomainEvent::WakeUp).await;
+                                }
+                            }
+                            None => break,
+                        }
+                    }
+                    _ = sleep_until(deadline) => {
+                        match awareness {
+                            Awareness::Awake => {
+                                info!("Idle for {dim_after:?}, dimming");
+                                awareness = Awareness::Dimmed;
+                                deadline = Instant::now() + sleep_after.saturating_sub(dim_after);
+                                state.broadcast(DomainEvent::Dim).await;
+                            }
+                            Awareness::Dimmed => {
+                                info!("Idle for {sleep_after:?}, sleeping");
+                                awareness = Awareness::Asleep;
+                                deadline = Instant::now() + Duration::from_secs(u32::MAX as u64);
+                                state.broadcast(DomainEvent::Sleep).await;
+                            }
+                            Awareness::Asleep => {}
+                        }
+                    }
+                }
+            }
+        }))
+    }
+}
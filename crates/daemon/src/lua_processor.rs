@@ -0,0 +1,168 @@
+use std::{path::PathBuf, sync::Mutex, time::SystemTime};
+
+use charon_lib::event::{DomainEvent, Mode};
+use mlua::{Function, Lua, LuaOptions, LuaSerdeExt, StdLib};
+use tracing::{error, info};
+
+use crate::domain::{ProcessorState, traits::Processor};
+
+/// Libraries exposed to user scripts: enough to write key-remaps and macros
+/// (tables, strings, numbers), nothing that reaches the filesystem,
+/// processes, or the debug API. Scripts are untrusted user input, not
+/// compiled Rust, so they never get `os`/`io`/`debug`/`ffi`. `StdLib`'s
+/// bitset operators aren't `const fn`, so this is a function rather than a
+/// `const`.
+fn sandboxed_libs() -> StdLib {
+    StdLib::TABLE | StdLib::STRING | StdLib::MATH
+}
+
+/// A [`Processor`] that delegates to a Lua script instead of compiled Rust.
+/// The script defines a global `on_event(event)` function that receives the
+/// incoming `DomainEvent` (as a Lua table via `mlua`'s serde bridge) and
+/// returns zero or more events to forward. The script file is watched and
+/// hot-reloaded whenever its mtime changes, so key-remaps, macros and
+/// conditional filters can be edited without recompiling or restarting the
+/// daemon.
+pub struct LuaProcessor {
+    state: ProcessorState,
+    name: String,
+    script_path: PathBuf,
+    // `mlua::Lua` is never `Sync`, but every `Processor` method takes `&mut
+    // self`, so this is never actually contended: the `Mutex` exists solely
+    // to make `LuaProcessor` satisfy `Box<dyn Processor + Send + Sync>`, and
+    // is always accessed through `get_mut`.
+    lua: Mutex<Lua>,
+    loaded_at: Option<SystemTime>,
+}
+
+impl LuaProcessor {
+    pub fn new(state: ProcessorState, name: impl Into<String>, script_path: PathBuf) -> Self {
+        let mut processor = Self {
+            state,
+            name: name.into(),
+            script_path,
+            lua: Mutex::new(sandboxed_lua()),
+            loaded_at: None,
+        };
+        processor.reload();
+        processor
+    }
+
+    pub fn ctor(
+        state: ProcessorState,
+        name: impl Into<String>,
+        script_path: PathBuf,
+    ) -> Box<dyn Processor + Send + Sync> {
+        Box::new(Self::new(state, name, script_path))
+    }
+
+    fn reload(&mut self) {
+        let source = match std::fs::read_to_string(&self.script_path) {
+            Ok(source) => source,
+            Err(err) => {
+                error!("Couldn't read Lua script {:?}: {err}", self.script_path);
+                return;
+            }
+        };
+
+        let lua = sandboxed_lua();
+        if let Err(err) = lua.load(&source).exec() {
+            error!("Lua script {:?} failed to load: {err}", self.script_path);
+            return;
+        }
+
+        *self.lua.get_mut().expect("lua mutex never contended") = lua;
+        self.loaded_at = std::fs::metadata(&self.script_path)
+            .and_then(|meta| meta.modified())
+            .ok();
+    }
+
+    fn reload_if_changed(&mut self) {
+        let modified = std::fs::metadata(&self.script_path).and_then(|meta| meta.modified());
+        if let Ok(modified) = modified {
+            if Some(modified) != self.loaded_at {
+                info!("Reloading Lua script: {:?}", self.script_path);
+                self.reload();
+            }
+        }
+    }
+}
+
+fn sandboxed_lua() -> Lua {
+    let lua = Lua::new_with(sandboxed_libs(), LuaOptions::default())
+        .expect("sandboxed Lua libs are always a valid combination");
+    // The base library is always loaded and brings `dofile`/`loadfile`,
+    // which read files from disk; remove them to keep the sandbox closed.
+    for name in ["dofile", "loadfile"] {
+        let _ = lua.globals().set(name, mlua::Value::Nil);
+    }
+    lua
+}
+
+impl Processor for LuaProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(&mut self, event: &DomainEvent) -> Vec<DomainEvent> {
+        self.reload_if_changed();
+
+        let state = &self.state;
+        let lua = self.lua.get_mut().expect("lua mutex never contended");
+        sync_state_globals(lua, state);
+
+        let on_event: Function = match lua.globals().get("on_event") {
+            Ok(f) => f,
+            Err(_) => return Vec::new(),
+        };
+
+        let event_value = match lua.to_value(event) {
+            Ok(value) => value,
+            Err(err) => {
+                error!("Couldn't convert event for Lua script: {err}");
+                return Vec::new();
+            }
+        };
+
+        match on_event.call::<mlua::Value>(event_value) {
+            Ok(result) => lua.from_value(result).unwrap_or_default(),
+            Err(err) => {
+                error!("Lua script {:?} raised an error: {err}", self.script_path);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Exposes the processor's [`ProcessorState`] to the script as globals: a
+/// `mode` string and a `config` table, so a script can e.g. no-op while
+/// `mode` is `"in_app"` instead of only ever seeing the raw event stream.
+fn sync_state_globals(lua: &Lua, state: &ProcessorState) {
+    let mode = match state.mode() {
+        Mode::PassThrough => "pass_through",
+        Mode::InApp => "in_app",
+    };
+    if let Err(err) = lua.globals().set("mode", mode) {
+        error!("Couldn't set Lua `mode` global: {err}");
+        return;
+    }
+
+    let config = match lua.create_table() {
+        Ok(table) => table,
+        Err(err) => {
+            error!("Couldn't create Lua `config` table: {err}");
+            return;
+        }
+    };
+    let _ = config.set(
+        "idle_dim_timeout_secs",
+        state.config().idle_dim_timeout().as_secs(),
+    );
+    let _ = config.set(
+        "idle_sleep_timeout_secs",
+        state.config().idle_sleep_timeout().as_secs(),
+    );
+    if let Err(err) = lua.globals().set("config", config) {
+        error!("Couldn't set Lua `config` global: {err}");
+    }
+}
@@ -0,0 +1,58 @@
+use charon_lib::{
+    event::DomainEvent,
+    text::{WootBuffer, WootId, WootOp},
+};
+
+use crate::domain::{ProcessorState, traits::Processor};
+
+/// Keeps a per-site [`WootBuffer`] converged with the rest of the cluster:
+/// local `SendText` input is turned into WOOT insert ops (`SendFile` produces
+/// no ops), and
+/// `TextOp`s arriving from other `KeyScanner`s or remote clients are
+/// integrated so concurrent edits never clobber each other.
+pub struct TextBufferProcessor {
+    state: ProcessorState,
+    buffer: WootBuffer,
+    tail: WootId,
+}
+
+impl TextBufferProcessor {
+    pub fn new(state: ProcessorState) -> Self {
+        let site_id = state.site_id();
+        Self {
+            state,
+            buffer: WootBuffer::new(site_id),
+            tail: WootId::START,
+        }
+    }
+
+    pub fn ctor(state: ProcessorState) -> Box<dyn Processor + Send + Sync> {
+        Box::new(Self::new(state))
+    }
+}
+
+impl Processor for TextBufferProcessor {
+    fn name(&self) -> &str {
+        "text-buffer"
+    }
+
+    fn process(&mut self, event: &DomainEvent) -> Vec<DomainEvent> {
+        match event {
+            DomainEvent::SendText(text) => text
+                .chars()
+                .map(|value| {
+                    let op = self.buffer.local_insert(self.tail, value);
+                    if let WootOp::Insert { id, .. } = op {
+                        self.tail = id;
+                    }
+                    DomainEvent::TextOp(op)
+                })
+                .collect(),
+            DomainEvent::TextOp(op) => {
+                self.buffer.integrate(op.clone());
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
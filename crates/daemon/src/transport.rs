@@ -0,0 +1,156 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use anyhow::Context;
+use charon_lib::auth::AuthChallenge;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{TcpListener, UnixListener},
+};
+use tokio_rustls::{TlsAcceptor, rustls};
+use tracing::{info, warn};
+
+/// Any duplex byte stream the daemon can speak the newline-delimited JSON
+/// `Event` protocol over, once a client has authenticated on it.
+pub trait Transport: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Transport for T {}
+
+/// Which transport(s) to listen on, read from `CharonConfig`. The Unix
+/// socket is always the default; TCP and TLS are opt-in so a remote TUI or
+/// companion app can connect over the network.
+#[derive(Debug, Clone)]
+pub enum ListenerConfig {
+    Unix(PathBuf),
+    Tcp(SocketAddr),
+    Tls {
+        addr: SocketAddr,
+        cert_chain: PathBuf,
+        private_key: PathBuf,
+    },
+}
+
+impl Default for ListenerConfig {
+    fn default() -> Self {
+        ListenerConfig::Unix(PathBuf::from("/run/charon/charon.sock"))
+    }
+}
+
+/// Binds `config` and, for every accepted connection, runs the
+/// challenge/response handshake against `secret` before handing the stream
+/// to `on_client`. Unauthorized clients never reach the `Event` loop.
+pub async fn serve<F, Fut>(
+    config: ListenerConfig,
+    secret: Arc<str>,
+    on_client: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(BufReader<Box<dyn Transport>>) -> Fut + Clone + Send + 'static,
+    Fut: std::future::Future<Output = ()> + Send + 'static,
+{
+    match config {
+        ListenerConfig::Unix(path) => {
+            let _ = std::fs::remove_file(&path);
+            let listener = UnixListener::bind(&path)
+                .with_context(|| format!("binding unix socket {path:?}"))?;
+            loop {
+                let (stream, _) = listener.accept().await?;
+                spawn_authenticated(Box::new(stream), secret.clone(), on_client.clone());
+            }
+        }
+        ListenerConfig::Tcp(addr) => {
+            let listener = TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("binding {addr}"))?;
+            loop {
+                let (stream, peer) = listener.accept().await?;
+                info!("Accepted TCP client from {peer}");
+                spawn_authenticated(Box::new(stream), secret.clone(), on_client.clone());
+            }
+        }
+        ListenerConfig::Tls {
+            addr,
+            cert_chain,
+            private_key,
+        } => {
+            let acceptor = TlsAcceptor::from(Arc::new(tls_server_config(
+                &cert_chain,
+                &private_key,
+            )?));
+            let listener = TcpListener::bind(addr)
+                .await
+                .with_context(|| format!("binding {addr}"))?;
+            loop {
+                let (stream, peer) = listener.accept().await?;
+                let acceptor = acceptor.clone();
+                match acceptor.accept(stream).await {
+                    Ok(tls_stream) => {
+                        info!("Accepted TLS client from {peer}");
+                        spawn_authenticated(Box::new(tls_stream), secret.clone(), on_client.clone());
+                    }
+                    Err(err) => warn!("TLS handshake with {peer} failed: {err}"),
+                }
+            }
+        }
+    }
+}
+
+fn spawn_authenticated<F, Fut>(stream: Box<dyn Transport>, secret: Arc<str>, on_client: F)
+where
+    F: Fn(BufReader<Box<dyn Transport>>) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        match authenticate(stream, &secret).await {
+            Ok(Some(reader)) => on_client(reader).await,
+            Ok(None) => warn!("Client failed the auth challenge; dropping connection"),
+            Err(err) => warn!("Auth handshake error: {err}"),
+        }
+    });
+}
+
+/// Runs the challenge/response handshake and hands back the same
+/// [`BufReader`] it ran the handshake on, rather than the raw stream: if the
+/// client pipelines its first `Event` right behind the auth response (normal
+/// for a buffered writer), those bytes land in this reader's internal buffer
+/// during `read_line` and would be silently dropped if we discarded it here.
+async fn authenticate(
+    stream: Box<dyn Transport>,
+    secret: &str,
+) -> anyhow::Result<Option<BufReader<Box<dyn Transport>>>> {
+    let mut reader = BufReader::new(stream);
+
+    let challenge = AuthChallenge::new();
+    let mut payload = serde_json::to_string(&challenge)?;
+    payload.push('\n');
+    reader.write_all(payload.as_bytes()).await?;
+    reader.flush().await?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let response = serde_json::from_str(line.trim()).context("malformed auth response")?;
+
+    Ok(if challenge.verify(secret, &response)? {
+        Some(reader)
+    } else {
+        None
+    })
+}
+
+fn tls_server_config(
+    cert_chain: &PathBuf,
+    private_key: &PathBuf,
+) -> anyhow::Result<rustls::ServerConfig> {
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(std::fs::File::open(
+        cert_chain,
+    )?))
+    .collect::<Result<Vec<_>, _>>()
+    .context("reading TLS cert chain")?;
+    let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(std::fs::File::open(
+        private_key,
+    )?))
+    .context("reading TLS private key")?
+    .ok_or_else(|| anyhow::anyhow!("no private key found in {private_key:?}"))?;
+
+    Ok(rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?)
+}
@@ -0,0 +1,65 @@
+mod topic;
+
+pub use topic::Topic;
+
+use serde::{Deserialize, Serialize};
+
+use crate::text::WootOp;
+
+/// Operating mode the daemon is in; drives which screen `AppManager` shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mode {
+    PassThrough,
+    InApp,
+}
+
+/// USB HID usage id of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HidKeyCode(pub u8);
+
+/// Bitset of held modifier keys (Ctrl/Shift/Alt/Super/...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Modifiers(pub u8);
+
+impl Modifiers {
+    pub fn contains(&self, other: &Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Every event that flows through the `EventBroker`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DomainEvent {
+    KeyPress(HidKeyCode, Modifiers),
+    KeyRelease(HidKeyCode, Modifiers),
+    HidReport([u8; 8]),
+    SendText(String),
+    SendFile(String, Vec<u8>),
+    /// A WOOT insert/delete op, broadcast so every replica converges.
+    TextOp(WootOp),
+    TextSent,
+    CurrentStats(String),
+
+    ModeChange(Mode),
+    Exit,
+    /// Idle long enough to dim the UI, but not long enough to sleep yet.
+    Dim,
+    Sleep,
+    WakeUp,
+
+    ReportSent(),
+}
+
+/// An event plus which actor originated it, as broadcast by the
+/// `EventBroker` and framed as newline-delimited JSON over the wire.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub origin: String,
+    pub payload: DomainEvent,
+}
+
+impl Event {
+    pub fn new(origin: String, payload: DomainEvent) -> Self {
+        Self { origin, payload }
+    }
+}
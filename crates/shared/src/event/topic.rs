@@ -21,11 +21,13 @@ impl From<&DomainEvent> for Topic {
             HidReport(_) => KeyOutput,
             SendText(_) => TextInput,
             SendFile(..) => TextInput,
+            TextOp(_) => TextInput,
             TextSent => Monitoring,
             CurrentStats(_) => Stats,
 
             ModeChange(_) => System,
             Exit => System,
+            Dim => System,
             Sleep => System,
             WakeUp => System,
 
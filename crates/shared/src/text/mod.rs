@@ -0,0 +1,3 @@
+mod woot;
+
+pub use woot::{WootBuffer, WootId, WootOp};
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Globally unique id of a WOOT character: the site that created it and that
+/// site's logical clock at creation time. Comparing two ids orders concurrent
+/// inserts deterministically, regardless of delivery order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct WootId {
+    pub site_id: u32,
+    pub clock: u32,
+}
+
+impl WootId {
+    /// Sentinel marking the start of the sequence. Never visible, never sent
+    /// over the wire as a real character.
+    pub const START: WootId = WootId {
+        site_id: 0,
+        clock: 0,
+    };
+
+    /// Sentinel marking the end of the sequence.
+    pub const END: WootId = WootId {
+        site_id: u32::MAX,
+        clock: u32::MAX,
+    };
+
+    pub fn new(site_id: u32, clock: u32) -> Self {
+        Self { site_id, clock }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WootNode {
+    prev_id: WootId,
+    next_id: WootId,
+    visible: bool,
+    value: char,
+}
+
+/// An insert or delete op, broadcast to other sites on the `TextInput` topic
+/// so every replica can integrate it and converge on the same text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WootOp {
+    Insert {
+        id: WootId,
+        prev_id: WootId,
+        next_id: WootId,
+        value: char,
+    },
+    Delete {
+        id: WootId,
+    },
+}
+
+/// A replicated text buffer implementing the WOOT CRDT. Characters form a
+/// doubly-linked sequence keyed by [`WootId`]; deletes are tombstones
+/// (`visible = false`) so later ops can still reference their id. An insert
+/// is only integrated once both its `prev_id` and `next_id` neighbors are
+/// already present locally; until then it waits in `pending`.
+#[derive(Debug)]
+pub struct WootBuffer {
+    site_id: u32,
+    clock: u32,
+    nodes: HashMap<WootId, WootNode>,
+    pending: Vec<WootOp>,
+}
+
+impl WootBuffer {
+    pub fn new(site_id: u32) -> Self {
+        let mut nodes = HashMap::new();
+        nodes.insert(
+            WootId::START,
+            WootNode {
+                prev_id: WootId::START,
+                next_id: WootId::END,
+                visible: false,
+                value: '\0',
+            },
+        );
+        nodes.insert(
+            WootId::END,
+            WootNode {
+                prev_id: WootId::START,
+                next_id: WootId::END,
+                visible: false,
+                value: '\0',
+            },
+        );
+        Self {
+            site_id,
+            clock: 0,
+            nodes,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Creates and integrates a local insert of `value` right after `after`.
+    /// Returns the op so the caller can broadcast it to other sites.
+    pub fn local_insert(&mut self, after: WootId, value: char) -> WootOp {
+        let next_id = self.nodes[&after].next_id;
+        self.clock += 1;
+        let op = WootOp::Insert {
+            id: WootId::new(self.site_id, self.clock),
+            prev_id: after,
+            next_id,
+            value,
+        };
+        self.integrate(op.clone());
+        op
+    }
+
+    /// Creates and integrates a local delete of `id`. Returns the op so the
+    /// caller can broadcast it to other sites.
+    pub fn local_delete(&mut self, id: WootId) -> WootOp {
+        let op = WootOp::Delete { id };
+        self.integrate(op.clone());
+        op
+    }
+
+    /// Integrates a local or remote op. Safe to call multiple times with the
+    /// same op (applying a delete twice is a no-op) and safe to call before
+    /// its neighbors arrive (it is queued in `pending` until they do).
+    pub fn integrate(&mut self, op: WootOp) {
+        match &op {
+            WootOp::Insert { id, prev_id, next_id, .. } => {
+                if self.nodes.contains_key(id) {
+                    return;
+                }
+                if !self.nodes.contains_key(prev_id) || !self.nodes.contains_key(next_id) {
+                    self.pending.push(op);
+                    return;
+                }
+                self.integrate_insert(op);
+            }
+            WootOp::Delete { id } => match self.nodes.get_mut(id) {
+                Some(node) => node.visible = false,
+                None => {
+                    self.pending.push(op);
+                    return;
+                }
+            },
+        }
+        self.retry_pending();
+    }
+
+    fn integrate_insert(&mut self, op: WootOp) {
+        let WootOp::Insert {
+            id,
+            prev_id,
+            next_id,
+            value,
+        } = op
+        else {
+            return;
+        };
+
+        let between = self.ids_between(prev_id, next_id);
+        if between.is_empty() {
+            self.nodes.get_mut(&prev_id).unwrap().next_id = id;
+            self.nodes.get_mut(&next_id).unwrap().prev_id = id;
+            self.nodes.insert(
+                id,
+                WootNode {
+                    prev_id,
+                    next_id,
+                    visible: true,
+                    value,
+                },
+            );
+            return;
+        }
+
+        // Concurrent inserts in the same gap are ordered by id: find the
+        // narrowest (left, right) bracket from `between` that still contains
+        // `id`, then recurse into that narrower gap.
+        let mut left = prev_id;
+        for candidate in between {
+            if candidate < id {
+                left = candidate;
+            } else {
+                break;
+            }
+        }
+        let right = self.nodes[&left].next_id;
+        self.integrate_insert(WootOp::Insert {
+            id,
+            prev_id: left,
+            next_id: right,
+            value,
+        });
+    }
+
+    /// Ids of every node (visible or tombstoned) currently between `prev_id`
+    /// and `next_id`, in list order.
+    fn ids_between(&self, prev_id: WootId, next_id: WootId) -> Vec<WootId> {
+        let mut ids = Vec::new();
+        let mut cursor = self.nodes[&prev_id].next_id;
+        while cursor != next_id {
+            ids.push(cursor);
+            cursor = self.nodes[&cursor].next_id;
+        }
+        ids
+    }
+
+    fn retry_pending(&mut self) {
+        let mut ready = Vec::new();
+        self.pending.retain(|op| {
+            let known = match op {
+                WootOp::Insert { prev_id, next_id, .. } => {
+                    self.nodes.contains_key(prev_id) && self.nodes.contains_key(next_id)
+                }
+                WootOp::Delete { id } => self.nodes.contains_key(id),
+            };
+            if known {
+                ready.push(op.clone());
+            }
+            !known
+        });
+        for op in ready {
+            self.integrate(op);
+        }
+    }
+
+    /// The converged, visible text, in sequence order.
+    pub fn to_string(&self) -> String {
+        self.ids_between(WootId::START, WootId::END)
+            .into_iter()
+            .filter_map(|id| self.nodes.get(&id))
+            .filter(|node| node.visible)
+            .map(|node| node.value)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_inserts_in_the_same_gap_converge_regardless_of_delivery_order() {
+        let mut site_a = WootBuffer::new(1);
+        let op_a = site_a.local_insert(WootId::START, 'a');
+
+        let mut site_b = WootBuffer::new(2);
+        let op_b = site_b.local_insert(WootId::START, 'b');
+
+        // Each site sees its own op first, then the remote one.
+        site_a.integrate(op_b.clone());
+        site_b.integrate(op_a.clone());
+
+        assert_eq!(site_a.to_string(), site_b.to_string());
+        // Lower (site_id, clock) wins the gap, independent of arrival order.
+        assert_eq!(site_a.to_string(), "ab");
+    }
+
+    #[test]
+    fn insert_arriving_before_its_neighbor_is_deferred_until_integrable() {
+        let mut origin = WootBuffer::new(1);
+        let op1 = origin.local_insert(WootId::START, 'x');
+        let WootOp::Insert { id: id1, .. } = op1 else {
+            unreachable!()
+        };
+        let op2 = origin.local_insert(id1, 'y');
+
+        let mut replica = WootBuffer::new(1);
+        // op2's prev_id is op1's id, which hasn't been integrated yet: it
+        // must be queued, not dropped or integrated against a missing node.
+        replica.integrate(op2.clone());
+        assert_eq!(replica.to_string(), "");
+
+        replica.integrate(op1.clone());
+        assert_eq!(replica.to_string(), origin.to_string());
+        assert_eq!(replica.to_string(), "xy");
+    }
+}